@@ -253,7 +253,7 @@ impl EngineCode {
 
 impl Drop for EngineCode {
     fn drop(&mut self) {
-        crate::module::unregister_code(self.original_code.raw_addr_range());
+        crate::module::unregister_code(&self.original_code, self.original_code.raw_addr_range());
     }
 }
 
@@ -309,7 +309,12 @@ enum StoreCodeStorage {
     /// This is the only reference to this CodeMemory. The StoreCode
     /// is owned directly by the Store's ModuleRegistry.
     #[cfg(feature = "debug")]
-    Private(Box<CodeMemory>),
+    Private {
+        code: Box<CodeMemory>,
+        /// The original code, which the private copy's address range
+        /// is registered with in the global code registry.
+        original: Arc<CodeMemory>,
+    },
 }
 
 impl StoreCode {
@@ -327,7 +332,10 @@ impl StoreCode {
             let mut private_copy = engine_code.original_code.deep_clone(engine)?;
             private_copy.publish()?;
             crate::module::register_code(&engine_code.original_code, private_copy.raw_addr_range());
-            StoreCodeStorage::Private(Box::new(private_copy))
+            StoreCodeStorage::Private {
+                code: Box::new(private_copy),
+                original: engine_code.original_code.clone(),
+            }
         } else {
             StoreCodeStorage::Shared(engine_code.original_code.clone())
         };
@@ -346,7 +354,7 @@ impl StoreCode {
         match &self.0 {
             StoreCodeStorage::Shared(m) => m,
             #[cfg(feature = "debug")]
-            StoreCodeStorage::Private(m) => m,
+            StoreCodeStorage::Private { code, .. } => code,
         }
     }
 
@@ -356,7 +364,7 @@ impl StoreCode {
     pub fn code_memory_mut(&mut self) -> Option<&mut CodeMemory> {
         match &mut self.0 {
             StoreCodeStorage::Shared(_) => None,
-            StoreCodeStorage::Private(m) => Some(m),
+            StoreCodeStorage::Private { code, .. } => Some(code),
         }
     }
 
@@ -380,8 +388,8 @@ impl Drop for StoreCode {
                 // above).
             }
             #[cfg(feature = "debug")]
-            StoreCodeStorage::Private(mem) => {
-                crate::module::unregister_code(mem.raw_addr_range());
+            StoreCodeStorage::Private { code, original } => {
+                crate::module::unregister_code(original, code.raw_addr_range());
             }
         }
     }
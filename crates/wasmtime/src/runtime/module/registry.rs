@@ -285,26 +285,52 @@ impl ModuleRegistry {
 // it is also automatically registered with the singleton global module
 // registry. When a `ModuleRegistry` is destroyed then all of its entries
 // are removed from the global registry.
+//
+// Note that the same range of code may be registered more than once at a
+// time. This happens, for example, when `Module::deserialize_raw` is invoked
+// multiple times with the same memory: each resulting `CodeMemory` refers to
+// the exact same text section. Each registration is therefore tracked
+// individually and removed by identity, and a range is only removed from the
+// map once all of its registrations are gone.
 fn global_code() -> &'static RwLock<GlobalRegistry> {
     static GLOBAL_CODE: OnceLock<RwLock<GlobalRegistry>> = OnceLock::new();
     GLOBAL_CODE.get_or_init(Default::default)
 }
 
-type GlobalRegistry = BTreeMap<usize, (usize, Arc<CodeMemory>)>;
+/// Map from the last (inclusive) address of a region of code to the
+/// registrations of that region.
+type GlobalRegistry = BTreeMap<usize, GlobalCode>;
+
+struct GlobalCode {
+    /// The first address of this region of code.
+    start: usize,
+
+    /// All live registrations of this exact region, in registration order.
+    ///
+    /// This is never empty: the entry is removed from the map when its last
+    /// registration is removed.
+    registrations: Vec<Arc<CodeMemory>>,
+}
 
 /// Find which registered region of code contains the given program counter, and
 /// what offset that PC is within that module's code.
+///
+/// If a region has multiple live registrations then the most recent one is
+/// returned.
 pub fn lookup_code(pc: usize) -> Option<(Arc<CodeMemory>, usize)> {
     let all_modules = global_code().read();
-    let (_end, (start, module)) = all_modules.range(pc..).next()?;
-    let text_offset = pc.checked_sub(*start)?;
-    Some((module.clone(), text_offset))
+    let (_end, code) = all_modules.range(pc..).next()?;
+    let text_offset = pc.checked_sub(code.start)?;
+    let image = code.registrations.last()?;
+    Some((image.clone(), text_offset))
 }
 
-/// Registers a new region of code.
+/// Registers a new region of code, described by `image`.
 ///
-/// Must not have been previously registered and must be `unregister`'d to
-/// prevent leaking memory.
+/// Must be unregistered with `unregister_code`, passing the same `image`, to
+/// prevent leaking memory. The same region may be registered multiple times,
+/// but a region must never partially overlap with a different registered
+/// region.
 ///
 /// This is required to enable traps to work correctly since the signal handler
 /// will lookup in the `GLOBAL_CODE` list to determine which a particular pc
@@ -315,20 +341,70 @@ pub fn register_code(image: &Arc<CodeMemory>, address: Range<usize>) {
     }
     let start = address.start;
     let end = address.end - 1;
-    let prev = global_code().write().insert(end, (start, image.clone()));
-    assert!(prev.is_none());
+
+    // Note that the assertion here happens after the lock is released so a
+    // failure doesn't poison the lock for the trap handler.
+    let overlaps = {
+        let mut global = global_code().write();
+
+        // The first region whose last address is at or after `start` is the
+        // only one which could overlap with `start..=end`. It must either be
+        // exactly this region or lie entirely after it.
+        let overlaps = match global.range(start..).next() {
+            Some((&other_end, other)) if other_end == end => other.start != start,
+            Some((_, other)) => other.start <= end,
+            None => false,
+        };
+        if !overlaps {
+            global
+                .entry(end)
+                .or_insert_with(|| GlobalCode {
+                    start,
+                    registrations: Vec::new(),
+                })
+                .registrations
+                .push(image.clone());
+        }
+        overlaps
+    };
+    assert!(!overlaps, "overlapping code registration");
 }
 
-/// Unregisters a code mmap from the global map.
+/// Unregisters a region of code from the global map.
 ///
-/// Must have been previously registered with `register`.
-pub fn unregister_code(address: Range<usize>) {
+/// Must have been previously registered with `register_code` with the same
+/// `image` and `address`.
+pub fn unregister_code(image: &Arc<CodeMemory>, address: Range<usize>) {
     if address.is_empty() {
         return;
     }
     let end = address.end - 1;
-    let code = global_code().write().remove(&end);
-    assert!(code.is_some());
+
+    // Note that the assertion here happens after the lock is released so a
+    // failure doesn't poison the lock for the trap handler, and the removed
+    // registration, if any, is also dropped outside of the lock.
+    let removed = {
+        let mut global = global_code().write();
+        match global.entry(end) {
+            Entry::Occupied(mut o) if o.get().start == address.start => {
+                let code = o.get_mut();
+                let removed = code
+                    .registrations
+                    .iter()
+                    .rposition(|r| Arc::ptr_eq(r, image))
+                    .map(|i| code.registrations.remove(i));
+                if code.registrations.is_empty() {
+                    o.remove();
+                }
+                removed
+            }
+            _ => None,
+        }
+    };
+    assert!(
+        removed.is_some(),
+        "unregistering code that was never registered"
+    );
 }
 
 #[test]
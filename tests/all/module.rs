@@ -583,40 +583,71 @@ fn validate_deterministic() {
     assert_eq!(result_parallel, result_sequential);
 }
 
-#[test]
-#[cfg_attr(miri, ignore)]
-fn deserialize_raw_avoids_copy() {
+/// Returns a Pulley engine along with a serialized module exporting an `add`
+/// function, suitable for passing to `Module::deserialize_raw`.
+fn serialized_pulley_add_module() -> (Engine, Vec<u8>) {
     // target pulley; executing code directly requires virtual memory
     let mut config = Config::new();
     let target = format!("{}", Triple::pulley_host());
     config.target(&target).unwrap();
     let engine = Engine::new(&config).unwrap();
-    let wat = String::from(
-        r#"
+    let wat = r#"
         (module
             (func (export "add") (param $lhs i32) (param $rhs i32) (result i32)
                 (i32.add (local.get $lhs) (local.get $rhs))
             )
         )
-        "#,
-    );
-    let module = Module::new(&engine, &wat).unwrap();
-    let mut serialized = module.serialize().expect("Serialize failed");
-    let serialized_ptr = std::ptr::slice_from_raw_parts(serialized.as_mut_ptr(), serialized.len());
-    let module_memory = std::ptr::NonNull::new(serialized_ptr.cast_mut()).unwrap();
+    "#;
+    let module = Module::new(&engine, wat).unwrap();
+    let serialized = module.serialize().expect("Serialize failed");
+    (engine, serialized)
+}
+
+/// Basic verification that a module from `serialized_pulley_add_module` works.
+fn assert_add_works(engine: &Engine, module: &Module) {
+    let mut store = Store::new(engine, ());
+    let instance = Instance::new(&mut store, module, &[]).unwrap();
+    let f = instance
+        .get_typed_func::<(i32, i32), i32>(&mut store, "add")
+        .unwrap();
+    assert_eq!(f.call(&mut store, (26, 50)).unwrap(), 76);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn deserialize_raw_avoids_copy() {
+    let (engine, mut serialized) = serialized_pulley_add_module();
+    let module_memory = std::ptr::NonNull::from(serialized.as_mut_slice());
     let deserialized_module =
         unsafe { Module::deserialize_raw(&engine, module_memory).expect("Deserialize Failed") };
 
     // assert that addresses haven't changed, no full copy
     // TODO: haven't been able to find a pub way of doing this
 
-    // basic verification that the loaded module works
-    let mut store = Store::new(&engine, ());
-    let instance = Instance::new(&mut store, &deserialized_module, &[]).unwrap();
-    let f = instance
-        .get_typed_func::<(i32, i32), i32>(&mut store, "add")
-        .unwrap();
-    assert_eq!(f.call(&mut store, (26, 50)).unwrap(), 76);
+    assert_add_works(&engine, &deserialized_module);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn deserialize_raw_same_memory_twice() {
+    let (engine, mut serialized) = serialized_pulley_add_module();
+    let module_memory = std::ptr::NonNull::from(serialized.as_mut_slice());
+
+    // Both modules share the same text section, so the same range of code is
+    // registered twice at the same time.
+    let first = unsafe { Module::deserialize_raw(&engine, module_memory).unwrap() };
+    let second = unsafe { Module::deserialize_raw(&engine, module_memory).unwrap() };
+    assert_add_works(&engine, &first);
+    assert_add_works(&engine, &second);
+
+    // Dropping one module must leave the other's registration intact.
+    drop(first);
+    assert_add_works(&engine, &second);
+    drop(second);
+
+    // And the range can be registered again once everything is gone.
+    let third = unsafe { Module::deserialize_raw(&engine, module_memory).unwrap() };
+    assert_add_works(&engine, &third);
 }
 
 #[test]